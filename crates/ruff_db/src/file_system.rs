@@ -0,0 +1,171 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+use camino::{Utf8Path, Utf8PathBuf};
+
+use crate::FxDashMap;
+
+/// Result type used by [`FileSystem`] operations.
+pub type Result<T> = std::io::Result<T>;
+
+/// A path to a file on the file system.
+///
+/// Unlike [`VfsPath`](crate::vfs::VfsPath), a `FileSystemPath` only ever refers to a path on disk,
+/// never to a vendored or virtual file.
+#[repr(transparent)]
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub struct FileSystemPath(Utf8Path);
+
+impl FileSystemPath {
+    pub fn new(path: &(impl AsRef<Utf8Path> + ?Sized)) -> &Self {
+        let path = path.as_ref();
+        // SAFETY: FileSystemPath is marked as #[repr(transparent)] so the conversion from a
+        // *const Utf8Path to a *const FileSystemPath is valid.
+        unsafe { &*(path as *const Utf8Path as *const FileSystemPath) }
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    pub fn extension(&self) -> Option<&str> {
+        self.0.extension()
+    }
+
+    pub fn parent(&self) -> Option<&FileSystemPath> {
+        self.0.parent().map(FileSystemPath::new)
+    }
+
+    pub fn join(&self, relative: impl AsRef<Utf8Path>) -> FileSystemPathBuf {
+        FileSystemPathBuf(self.0.join(relative))
+    }
+
+    pub fn to_path_buf(&self) -> FileSystemPathBuf {
+        FileSystemPathBuf(self.0.to_path_buf())
+    }
+
+    pub fn as_std_path(&self) -> &std::path::Path {
+        self.0.as_std_path()
+    }
+}
+
+impl AsRef<FileSystemPath> for FileSystemPath {
+    #[inline]
+    fn as_ref(&self) -> &FileSystemPath {
+        self
+    }
+}
+
+impl AsRef<FileSystemPath> for str {
+    #[inline]
+    fn as_ref(&self) -> &FileSystemPath {
+        FileSystemPath::new(self)
+    }
+}
+
+impl AsRef<FileSystemPath> for String {
+    #[inline]
+    fn as_ref(&self) -> &FileSystemPath {
+        FileSystemPath::new(self)
+    }
+}
+
+#[repr(transparent)]
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+pub struct FileSystemPathBuf(Utf8PathBuf);
+
+impl FileSystemPathBuf {
+    pub fn new() -> Self {
+        Self(Utf8PathBuf::new())
+    }
+
+    pub fn as_path(&self) -> &FileSystemPath {
+        FileSystemPath::new(&self.0)
+    }
+}
+
+impl Default for FileSystemPathBuf {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AsRef<FileSystemPath> for FileSystemPathBuf {
+    fn as_ref(&self) -> &FileSystemPath {
+        self.as_path()
+    }
+}
+
+impl Deref for FileSystemPathBuf {
+    type Target = FileSystemPath;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_path()
+    }
+}
+
+impl From<&str> for FileSystemPathBuf {
+    fn from(value: &str) -> Self {
+        Self(Utf8PathBuf::from(value))
+    }
+}
+
+/// Abstraction over the file system that gives access to the content and metadata of files on the file system.
+///
+/// Implementations can, for example, use the OS file system or an in-memory file system for testing.
+pub trait FileSystem {
+    /// Reads the content of the file at `path` into a `String`.
+    fn read(&self, path: &FileSystemPath) -> Result<String>;
+
+    /// Returns `true` if `path` exists on the file system.
+    fn exists(&self, path: &FileSystemPath) -> bool;
+}
+
+/// File system that stores all content in memory, useful for testing.
+#[derive(Debug, Default, Clone)]
+pub struct MemoryFileSystem {
+    inner: Arc<FxDashMap<FileSystemPathBuf, String>>,
+}
+
+impl MemoryFileSystem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writes the content of the file at `path`, creating it if it doesn't yet exist.
+    pub fn write_file(
+        &mut self,
+        path: impl AsRef<FileSystemPath>,
+        content: String,
+    ) -> Result<()> {
+        self.inner
+            .insert(path.as_ref().to_path_buf(), content);
+        Ok(())
+    }
+
+    /// Removes the file at `path`, as if it had been deleted on disk.
+    pub fn remove_file(&mut self, path: impl AsRef<FileSystemPath>) -> Result<()> {
+        self.inner.remove(&path.as_ref().to_path_buf());
+        Ok(())
+    }
+
+    /// Creates a cheap, `Arc`-backed copy of the file system for use in a Salsa snapshot.
+    pub fn snapshot(&self) -> Self {
+        self.clone()
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn read(&self, path: &FileSystemPath) -> Result<String> {
+        self.inner
+            .get(&path.to_path_buf())
+            .map(|entry| entry.value().clone())
+            .ok_or_else(|| {
+                std::io::Error::new(std::io::ErrorKind::NotFound, path.as_str().to_string())
+            })
+    }
+
+    fn exists(&self, path: &FileSystemPath) -> bool {
+        self.inner.contains_key(&path.to_path_buf())
+    }
+}
@@ -4,11 +4,16 @@ use rustc_hash::FxHasher;
 use salsa::DbWithJar;
 
 use crate::file_system::FileSystem;
+use crate::loader::LoaderEvent;
 use crate::parsed::parsed_module;
 use crate::source::{line_index, source_text};
-use crate::vfs::{Vfs, VfsFile};
+use crate::vfs::{
+    join_normalized, system_path_to_file, vendored_path_to_file, AnchoredPath, FileRevision, Vfs,
+    VfsFile, VfsPath,
+};
 
 pub mod file_system;
+pub mod loader;
 pub mod parsed;
 pub mod source;
 pub mod vfs;
@@ -23,6 +28,53 @@ pub trait Db: DbWithJar<Jar> {
     fn file_system(&self) -> &dyn FileSystem;
 
     fn vfs(&self) -> &Vfs;
+
+    /// Applies a [`LoaderEvent`] reported by a [`Loader`](crate::loader::Loader), bumping the
+    /// revision of the corresponding [`VfsFile`] so that Salsa recomputes any tracked query that
+    /// depends on it.
+    ///
+    /// A deletion does nothing if the event's path hasn't been turned into a `VfsFile` yet, since
+    /// there's nothing to invalidate. A creation or change creates the `VfsFile` on first sight if
+    /// the path exists on the file system, and does nothing if it doesn't.
+    fn apply_loader_event(&mut self, event: LoaderEvent) {
+        let exists = !matches!(event, LoaderEvent::Deleted(_));
+
+        // A deletion means the path no longer exists on disk, so looking it up through
+        // `system_path_to_file` (which only hands back files that currently exist) would always
+        // miss. Go through the `Vfs`'s interner instead, which still remembers the `VfsFile` for
+        // a path after it's gone.
+        let file = if exists {
+            system_path_to_file(&*self, event.path())
+        } else {
+            self.vfs().try_file(&VfsPath::file_system(event.path()))
+        };
+
+        let Some(file) = file else {
+            return;
+        };
+
+        let new_revision = FileRevision::new(file.revision(&*self).as_u64() + 1);
+        file.set_revision(self).to(new_revision);
+        file.set_exists(self).to(exists);
+    }
+
+    /// Resolves `path`'s relative segment against the directory containing its anchor file and
+    /// returns the [`VfsFile`] at the resulting location, if any.
+    ///
+    /// This is the entry point callers should reach for to express "the module next to *this*
+    /// file" as a single [`AnchoredPath`] value; it composes with
+    /// [`FileSet::resolve_path`](crate::vfs::file_set::FileSet::resolve_path) for callers that
+    /// have already partitioned the `Vfs` into [`FileSet`](crate::vfs::file_set::FileSet)s.
+    fn resolve(&self, path: AnchoredPath) -> Option<VfsFile> {
+        let anchor_path = path.anchor.path(self);
+        let resolved = join_normalized(&anchor_path, path.path)?;
+
+        match &resolved {
+            VfsPath::FileSystem(fs_path) => system_path_to_file(self, fs_path),
+            VfsPath::Vendored(vendored_path) => vendored_path_to_file(self, vendored_path),
+            VfsPath::Virtual(_) => None,
+        }
+    }
 }
 
 /// Trait for upcasting a reference to a base trait object.
@@ -37,6 +89,7 @@ mod tests {
     use salsa::DebugWithDb;
 
     use crate::file_system::{FileSystem, MemoryFileSystem};
+    use crate::loader::test::TestLoader;
     use crate::vfs::{VendoredPathBuf, Vfs};
     use crate::{Db, Jar};
 
@@ -48,6 +101,7 @@ mod tests {
         storage: salsa::Storage<Self>,
         vfs: Vfs,
         file_system: MemoryFileSystem,
+        loader: TestLoader,
         events: std::sync::Arc<std::sync::Mutex<Vec<salsa::Event>>>,
     }
 
@@ -59,6 +113,7 @@ mod tests {
             Self {
                 storage: salsa::Storage::default(),
                 file_system: MemoryFileSystem::default(),
+                loader: TestLoader::new(),
                 events: std::sync::Arc::default(),
                 vfs,
             }
@@ -98,6 +153,20 @@ mod tests {
         pub(crate) fn vfs_mut(&mut self) -> &mut Vfs {
             &mut self.vfs
         }
+
+        /// Queues a synthetic loader event, to be picked up by [`TestDb::sync_loader`].
+        #[allow(unused)]
+        pub(crate) fn push_loader_event(&mut self, event: crate::loader::LoaderEvent) {
+            self.loader.push(event);
+        }
+
+        /// Applies every loader event queued so far, invalidating the `VfsFile`s they target.
+        #[allow(unused)]
+        pub(crate) fn sync_loader(&mut self) {
+            while let Some(event) = self.loader.pop() {
+                self.apply_loader_event(event);
+            }
+        }
     }
 
     impl Db for TestDb {
@@ -124,8 +193,80 @@ mod tests {
                 storage: self.storage.snapshot(),
                 file_system: self.file_system.snapshot(),
                 vfs: self.vfs.snapshot(),
+                loader: self.loader.clone(),
                 events: self.events.clone(),
             })
         }
     }
+
+    #[test]
+    fn resolve_finds_a_sibling_file() -> crate::file_system::Result<()> {
+        use crate::vfs::{system_path_to_file, AnchoredPath};
+
+        let mut db = TestDb::new();
+
+        db.file_system_mut()
+            .write_file("src/a.py", String::new())?;
+        db.file_system_mut()
+            .write_file("src/b.py", String::new())?;
+
+        let anchor = system_path_to_file(&db, "src/a.py").unwrap();
+
+        let resolved = db.resolve(AnchoredPath::new(anchor, "b.py"));
+
+        assert_eq!(resolved, system_path_to_file(&db, "src/b.py"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_is_none_for_a_virtual_anchor() {
+        use crate::vfs::{virtual_file, AnchoredPath};
+        use ruff_text_size::TextRange;
+
+        let mut db = TestDb::new();
+        db.file_system_mut()
+            .write_file("a.py", "x = 1".to_string())
+            .unwrap();
+
+        let parent = crate::vfs::system_path_to_file(&db, "a.py").unwrap();
+        let anchor = virtual_file(&db, parent, TextRange::new(0.into(), 1.into()));
+
+        assert_eq!(db.resolve(AnchoredPath::new(anchor, "b.py")), None);
+    }
+
+    #[test]
+    fn set_file_contents_applies_a_batch_in_one_pass() -> crate::file_system::Result<()> {
+        use crate::vfs::{system_path_to_file, VfsPath};
+
+        let mut db = TestDb::new();
+
+        db.file_system_mut()
+            .write_file("a.py", "x = 1".to_string())?;
+        db.file_system_mut()
+            .write_file("b.py", "x = 2".to_string())?;
+
+        let a = system_path_to_file(&db, "a.py").unwrap();
+        let b = system_path_to_file(&db, "b.py").unwrap();
+
+        let a_revision = a.revision(&db);
+        let b_revision = b.revision(&db);
+
+        let vfs = db.vfs().clone();
+        vfs.set_file_contents(
+            &mut db,
+            [
+                (VfsPath::file_system("a.py"), true),
+                (VfsPath::file_system("b.py"), false),
+            ],
+        );
+
+        assert_ne!(a.revision(&db), a_revision);
+        assert!(a.exists(&db));
+
+        assert_ne!(b.revision(&db), b_revision);
+        assert!(!b.exists(&db));
+
+        Ok(())
+    }
 }
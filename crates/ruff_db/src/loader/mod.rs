@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use crate::file_system::FileSystemPathBuf;
+
+mod notify;
+#[cfg(test)]
+pub(crate) mod test;
+
+pub use notify::NotifyLoader;
+
+/// The window within which consecutive change events for the same path are collapsed into one,
+/// so that an editor save-storm only triggers a single invalidation.
+pub(crate) const DEBOUNCE_WINDOW: Duration = Duration::from_millis(100);
+
+/// A file-system change, as reported by a [`Loader`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LoaderEvent {
+    Created(FileSystemPathBuf),
+    Changed(FileSystemPathBuf),
+    Deleted(FileSystemPathBuf),
+}
+
+impl LoaderEvent {
+    /// Returns the path that this event is about.
+    pub fn path(&self) -> &FileSystemPathBuf {
+        match self {
+            LoaderEvent::Created(path) | LoaderEvent::Changed(path) | LoaderEvent::Deleted(path) => {
+                path
+            }
+        }
+    }
+}
+
+/// Watches a set of root directories for file-system changes and streams them back as
+/// debounced [`LoaderEvent`]s.
+///
+/// A [`Db`](crate::Db) consumes these events through [`Db::apply_loader_event`](crate::Db::apply_loader_event),
+/// which bumps the revision of the corresponding [`VfsFile`](crate::vfs::VfsFile) so dependent
+/// tracked queries recompute on next access.
+pub trait Loader {
+    /// Replaces the set of root directories being watched.
+    fn set_roots(&mut self, roots: Vec<FileSystemPathBuf>);
+
+    /// Blocks until the next debounced change event is available.
+    fn next_event(&mut self) -> LoaderEvent;
+}
+
+#[cfg(test)]
+mod tests {
+    use salsa::EventKind;
+
+    use crate::loader::LoaderEvent;
+    use crate::source::source_text;
+    use crate::tests::TestDb;
+    use crate::vfs::system_path_to_file;
+
+    #[test]
+    fn changed_file_invalidates_source_text() -> crate::file_system::Result<()> {
+        let mut db = TestDb::new();
+        let path = "test.py";
+
+        db.file_system_mut().write_file(path, "x = 1".to_string())?;
+        let file = system_path_to_file(&db, path).unwrap();
+
+        assert_eq!(source_text(&db, file), "x = 1");
+
+        db.clear_salsa_events();
+        db.file_system_mut().write_file(path, "x = 2".to_string())?;
+        db.push_loader_event(LoaderEvent::Changed(path.into()));
+        db.sync_loader();
+
+        assert_eq!(source_text(&db, file), "x = 2");
+
+        let events = db.take_salsa_events();
+        assert!(events
+            .iter()
+            .any(|event| matches!(event.kind, EventKind::WillExecute { .. })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn deleted_file_invalidates_source_text() -> crate::file_system::Result<()> {
+        let mut db = TestDb::new();
+        let path = "test.py";
+
+        db.file_system_mut().write_file(path, "x = 1".to_string())?;
+        let file = system_path_to_file(&db, path).unwrap();
+
+        assert_eq!(source_text(&db, file), "x = 1");
+        assert!(file.exists(&db));
+
+        db.file_system_mut().remove_file(path)?;
+        db.push_loader_event(LoaderEvent::Deleted(path.into()));
+        db.sync_loader();
+
+        assert!(!file.exists(&db));
+        assert_eq!(source_text(&db, file), "");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::VecDeque;
+use std::sync::mpsc::{channel, Receiver};
+
+use ::notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use rustc_hash::FxHashMap;
+
+use crate::file_system::FileSystemPathBuf;
+use crate::loader::{Loader, LoaderEvent, DEBOUNCE_WINDOW};
+
+/// [`Loader`] backed by the operating system's native file-watching facility: inotify on Linux,
+/// FSEvents on macOS, and `ReadDirectoryChangesW` on Windows (via the cross-platform `notify`
+/// crate).
+pub struct NotifyLoader {
+    watcher: RecommendedWatcher,
+    events: Receiver<::notify::Result<::notify::Event>>,
+    /// Debounced events waiting to be handed out one at a time by [`NotifyLoader::next_event`].
+    pending: VecDeque<LoaderEvent>,
+    /// The roots currently being watched, unwatched again by the next [`Loader::set_roots`].
+    roots: Vec<FileSystemPathBuf>,
+}
+
+impl NotifyLoader {
+    pub fn new() -> ::notify::Result<Self> {
+        let (sender, events) = channel();
+        let watcher = ::notify::recommended_watcher(move |event| {
+            // The receiving end only goes away together with `self`, so a failed send just
+            // means we're shutting down.
+            let _ = sender.send(event);
+        })?;
+
+        Ok(Self {
+            watcher,
+            events,
+            pending: VecDeque::new(),
+            roots: Vec::new(),
+        })
+    }
+}
+
+impl Loader for NotifyLoader {
+    fn set_roots(&mut self, roots: Vec<FileSystemPathBuf>) {
+        for old_root in std::mem::take(&mut self.roots) {
+            let _ = self.watcher.unwatch(old_root.as_std_path());
+        }
+
+        for root in roots {
+            // Errors (e.g. a root that no longer exists) are ignored; the loader simply skips
+            // watching that root.
+            if self
+                .watcher
+                .watch(root.as_std_path(), RecursiveMode::Recursive)
+                .is_ok()
+            {
+                self.roots.push(root);
+            }
+        }
+    }
+
+    fn next_event(&mut self) -> LoaderEvent {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return event;
+            }
+
+            let Ok(Ok(event)) = self.events.recv() else {
+                continue;
+            };
+
+            // Debounce: a save-storm can report several events in a row, possibly touching more
+            // than one path (e.g. a formatter rewriting several files, or a git checkout).
+            // Collapse repeats of the same path into a single event, keyed by path, rather than
+            // discarding every event but the first.
+            let mut coalesced: FxHashMap<FileSystemPathBuf, LoaderEvent> = FxHashMap::default();
+
+            if let Some(loader_event) = to_loader_event(event) {
+                coalesced.insert(loader_event.path().clone(), loader_event);
+            }
+
+            while let Ok(Ok(event)) = self.events.recv_timeout(DEBOUNCE_WINDOW) {
+                if let Some(loader_event) = to_loader_event(event) {
+                    coalesced.insert(loader_event.path().clone(), loader_event);
+                }
+            }
+
+            self.pending.extend(coalesced.into_values());
+        }
+    }
+}
+
+fn to_loader_event(event: ::notify::Event) -> Option<LoaderEvent> {
+    let path = event.paths.into_iter().next()?;
+    let path = FileSystemPathBuf::from(path.to_str()?);
+
+    match event.kind {
+        EventKind::Create(_) => Some(LoaderEvent::Created(path)),
+        EventKind::Remove(_) => Some(LoaderEvent::Deleted(path)),
+        EventKind::Modify(_) => Some(LoaderEvent::Changed(path)),
+        // `Access` fires on plain reads (e.g. inotify's `IN_ACCESS`), not content changes, and
+        // `Any`/`Other` don't tell us enough to invalidate anything; surfacing them as `Changed`
+        // would spuriously recompute every tracked query that depends on `path`.
+        EventKind::Access(_) | EventKind::Any | EventKind::Other => None,
+    }
+}
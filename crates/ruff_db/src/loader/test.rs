@@ -0,0 +1,47 @@
+use std::collections::VecDeque;
+
+use crate::file_system::FileSystemPathBuf;
+use crate::loader::{Loader, LoaderEvent};
+
+/// Deterministic [`Loader`] for tests.
+///
+/// Events are queued with [`TestLoader::push`] and replayed in order, which lets a [`TestDb`]
+/// feed synthetic file-system changes and assert the Salsa events that result from applying
+/// them.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct TestLoader {
+    roots: Vec<FileSystemPathBuf>,
+    pending: VecDeque<LoaderEvent>,
+}
+
+impl TestLoader {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues `event` to be returned by a future call to [`Loader::next_event`].
+    pub(crate) fn push(&mut self, event: LoaderEvent) {
+        self.pending.push_back(event);
+    }
+
+    /// Removes and returns the next queued event, if any, without blocking.
+    pub(crate) fn pop(&mut self) -> Option<LoaderEvent> {
+        self.pending.pop_front()
+    }
+
+    #[allow(unused)]
+    pub(crate) fn roots(&self) -> &[FileSystemPathBuf] {
+        &self.roots
+    }
+}
+
+impl Loader for TestLoader {
+    fn set_roots(&mut self, roots: Vec<FileSystemPathBuf>) {
+        self.roots = roots;
+    }
+
+    fn next_event(&mut self) -> LoaderEvent {
+        self.pop()
+            .expect("test should push an event before calling `next_event`")
+    }
+}
@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 use ruff_python_ast::{ModModule, PySourceType};
 use ruff_python_parser::{parse_unchecked_source, Parsed};
+use ruff_text_size::{TextRange, TextSize};
 
 use crate::source::source_text;
 use crate::vfs::{VfsFile, VfsPath};
@@ -15,6 +16,15 @@ use crate::Db;
 /// AST even if the file contains syntax errors. The parse errors
 /// are then accessible through [`Parsed::errors`].
 ///
+/// **Every range in the returned [`ParsedModule`] — AST node ranges, token ranges, parse error
+/// ranges — is relative to `file`'s own source text, not the file it's embedded in.** For a
+/// [`VfsPath::Virtual`] file this means ranges are relative to the start of the slice, *not* to
+/// the parent file's source text, because the parser only ever sees the slice-relative text.
+/// Callers that need a location in the parent's coordinate space (e.g. to point a diagnostic at
+/// the real location in the file the user has open) must pass every range they read from this
+/// `ParsedModule` through [`ParsedModule::to_parent_range`] before using it; reading `.syntax()`
+/// ranges directly and handing them to the parent file is a bug.
+///
 /// The query is only cached when the [`source_text()`] hasn't changed. This is because
 /// comparing two ASTs is a non-trivial operation and every offset change is directly
 /// reflected in the changed AST offsets.
@@ -25,26 +35,31 @@ pub fn parsed_module(db: &dyn Db, file: VfsFile) -> ParsedModule {
     let source = source_text(db, file);
     let path = file.path(db);
 
-    let ty = match path {
-        VfsPath::FileSystem(path) => path
-            .extension()
-            .map_or(PySourceType::Python, PySourceType::from_extension),
-        VfsPath::Vendored(_) => PySourceType::Stub,
+    let (ty, base_offset) = match &path {
+        VfsPath::FileSystem(path) => (
+            path.extension()
+                .map_or(PySourceType::Python, PySourceType::from_extension),
+            TextSize::default(),
+        ),
+        VfsPath::Vendored(_) => (PySourceType::Stub, TextSize::default()),
+        VfsPath::Virtual(virtual_path) => (PySourceType::Python, virtual_path.range().start()),
     };
 
-    ParsedModule::new(parse_unchecked_source(&source, ty))
+    ParsedModule::new(parse_unchecked_source(&source, ty), base_offset)
 }
 
 /// Cheap cloneable wrapper around the parsed module.
 #[derive(Clone, PartialEq)]
 pub struct ParsedModule {
     inner: Arc<Parsed<ModModule>>,
+    base_offset: TextSize,
 }
 
 impl ParsedModule {
-    pub fn new(parsed: Parsed<ModModule>) -> Self {
+    pub fn new(parsed: Parsed<ModModule>, base_offset: TextSize) -> Self {
         Self {
             inner: Arc::new(parsed),
+            base_offset,
         }
     }
 
@@ -52,6 +67,16 @@ impl ParsedModule {
     pub fn into_arc(self) -> Arc<Parsed<ModModule>> {
         self.inner
     }
+
+    /// Translates `range`, expressed in this module's own (slice-relative) source text, into the
+    /// coordinate space of the file that owns it.
+    ///
+    /// This is the identity translation unless the module was parsed from a
+    /// [`VfsPath::Virtual`] slice, in which case `range` needs shifting by the slice's offset
+    /// into the parent file.
+    pub fn to_parent_range(&self, range: TextRange) -> TextRange {
+        range + self.base_offset
+    }
 }
 
 impl Deref for ParsedModule {
@@ -70,11 +95,13 @@ impl std::fmt::Debug for ParsedModule {
 
 #[cfg(test)]
 mod tests {
+    use ruff_text_size::TextRange;
+
     use crate::file_system::FileSystemPath;
     use crate::parsed::parsed_module;
     use crate::tests::TestDb;
     use crate::vfs::VendoredPath;
-    use crate::vfs::{system_path_to_file, vendored_path_to_file};
+    use crate::vfs::{system_path_to_file, vendored_path_to_file, virtual_file};
 
     #[test]
     fn python_file() -> crate::file_system::Result<()> {
@@ -132,4 +159,28 @@ else:
 
         assert!(parsed.is_valid());
     }
+
+    #[test]
+    fn to_parent_range_offsets_by_the_slices_start_in_the_parent() -> crate::file_system::Result<()>
+    {
+        let mut db = TestDb::new();
+        let path = "test.py";
+
+        db.file_system_mut()
+            .write_file(path, "outer x = 10 end".to_string())?;
+
+        let parent = system_path_to_file(&db, path).unwrap();
+        let slice_range = TextRange::new(6.into(), 12.into());
+        let slice = virtual_file(&db, parent, slice_range);
+
+        let parsed = parsed_module(&db, slice);
+        let local_range = TextRange::new(0.into(), 1.into());
+
+        assert_eq!(
+            parsed.to_parent_range(local_range),
+            local_range + slice_range.start()
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,83 @@
+use ruff_source_file::LineIndex;
+
+use crate::vfs::{VfsFile, VfsPath};
+use crate::Db;
+
+/// Returns the source text of `file`.
+///
+/// For a [`VfsPath::Virtual`] file, this is the slice of the parent file's source text that the
+/// virtual file was created from. Querying it depends on the parent's `source_text`, so editing
+/// the parent correctly invalidates the virtual file's source text (and everything derived from
+/// it) too.
+///
+/// The query is only cached until the file's revision changes, e.g. because the file's content
+/// was edited or the file was deleted.
+#[salsa::tracked]
+pub fn source_text(db: &dyn Db, file: VfsFile) -> String {
+    let path = file.path(db);
+    // Read the revision so that bumping it (e.g. for a loader event) invalidates this query.
+    let _ = file.revision(db);
+
+    let content = match &path {
+        VfsPath::FileSystem(path) => db.file_system().read(path).ok(),
+        VfsPath::Vendored(path) => db.vfs().read_vendored(path).map(|content| content.to_string()),
+        VfsPath::Virtual(virtual_path) => {
+            let parent_source = source_text(db, virtual_path.parent());
+            let range = virtual_path.range();
+
+            // The parent may have been edited since the virtual file was carved out of it, so
+            // `range` can now fall outside `parent_source` or no longer sit on a char boundary.
+            // Degrade to empty content instead of panicking; since this depends on the parent's
+            // `source_text`, it's recomputed on the next edit to the parent.
+            parent_source
+                .get(range.start().to_usize()..range.end().to_usize())
+                .map(str::to_string)
+        }
+    };
+
+    content.unwrap_or_default()
+}
+
+/// Returns the line index for `file`, used to translate between byte offsets and line/column
+/// positions.
+#[salsa::tracked(return_ref)]
+pub fn line_index(db: &dyn Db, file: VfsFile) -> LineIndex {
+    let source = source_text(db, file);
+
+    LineIndex::from_source_text(&source)
+}
+
+#[cfg(test)]
+mod tests {
+    use ruff_text_size::TextRange;
+
+    use crate::loader::LoaderEvent;
+    use crate::source::source_text;
+    use crate::tests::TestDb;
+    use crate::vfs::{system_path_to_file, virtual_file};
+
+    #[test]
+    fn virtual_file_source_text_tracks_the_parent() -> crate::file_system::Result<()> {
+        let mut db = TestDb::new();
+        let path = "test.py";
+        let content = "outer body {} end";
+
+        db.file_system_mut().write_file(path, content.to_string())?;
+        let parent = system_path_to_file(&db, path).unwrap();
+
+        let range = TextRange::new(6.into(), 13.into());
+        let slice = virtual_file(&db, parent, range);
+
+        assert_eq!(source_text(&db, slice), "body {}");
+
+        db.file_system_mut().write_file(path, "short".to_string())?;
+        db.push_loader_event(LoaderEvent::Changed(path.into()));
+        db.sync_loader();
+
+        // The parent shrank below the slice's original range: degrade to empty instead of
+        // panicking on the stale range.
+        assert_eq!(source_text(&db, slice), "");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+use rustc_hash::FxHashMap;
+
+use crate::vfs::{join_normalized, VfsFile, VfsPath};
+
+/// A partition of the [`Vfs`](super::Vfs)'s known files into a single, disjoint root.
+///
+/// `FileSet` is the building block, not a registry: it only tracks the files explicitly
+/// [`insert`](FileSet::insert)ed into it. Typical uses are one `FileSet` for the first-party
+/// source root and another for the vendored typeshed stubs; a "default" set for anything not
+/// otherwise partitioned is just another plain `FileSet` instance, not a built-in fallback.
+#[derive(Debug, Default)]
+pub struct FileSet {
+    by_path: FxHashMap<VfsPath, VfsFile>,
+    by_file: FxHashMap<VfsFile, VfsPath>,
+}
+
+impl FileSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts `file` at `path` into the set.
+    pub fn insert(&mut self, path: VfsPath, file: VfsFile) {
+        self.by_file.insert(file, path.clone());
+        self.by_path.insert(path, file);
+    }
+
+    /// Returns the file stored at `path`, if any.
+    pub fn file(&self, path: &VfsPath) -> Option<VfsFile> {
+        self.by_path.get(path).copied()
+    }
+
+    /// Returns the path that `file` was inserted with.
+    pub fn path(&self, file: VfsFile) -> Option<&VfsPath> {
+        self.by_file.get(&file)
+    }
+
+    /// Resolves `relative` against the directory containing `anchor`'s path, normalizing any
+    /// `.`/`..` segments, and returns the file at the resulting path if it belongs to this set.
+    ///
+    /// Returns `None` if `anchor` isn't part of this set or the resolved path isn't known to it.
+    pub fn resolve_path(&self, anchor: VfsFile, relative: &str) -> Option<VfsFile> {
+        let anchor_path = self.path(anchor)?;
+        let resolved = join_normalized(anchor_path, relative)?;
+
+        self.file(&resolved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::TestDb;
+    use crate::vfs::file_set::FileSet;
+    use crate::vfs::{system_path_to_file, VfsPath};
+
+    #[test]
+    fn resolve_path_normalizes_parent_dir_segments() -> crate::file_system::Result<()> {
+        let mut db = TestDb::new();
+
+        db.file_system_mut()
+            .write_file("src/a/mod.py", String::new())?;
+        db.file_system_mut().write_file("src/b.py", String::new())?;
+
+        let anchor = system_path_to_file(&db, "src/a/mod.py").unwrap();
+        let target = system_path_to_file(&db, "src/b.py").unwrap();
+
+        let mut set = FileSet::new();
+        set.insert(VfsPath::file_system("src/a/mod.py"), anchor);
+        set.insert(VfsPath::file_system("src/b.py"), target);
+
+        assert_eq!(set.resolve_path(anchor, "../b.py"), Some(target));
+
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_path_is_none_if_the_resolved_path_is_outside_the_set() -> crate::file_system::Result<()>
+    {
+        let mut db = TestDb::new();
+        db.file_system_mut().write_file("src/a.py", String::new())?;
+
+        let anchor = system_path_to_file(&db, "src/a.py").unwrap();
+
+        let mut set = FileSet::new();
+        set.insert(VfsPath::file_system("src/a.py"), anchor);
+
+        assert_eq!(set.resolve_path(anchor, "../b.py"), None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,315 @@
+use std::sync::{Arc, Mutex};
+
+use camino::{Utf8Component, Utf8Path, Utf8PathBuf};
+use rustc_hash::FxHashMap;
+use ruff_text_size::TextRange;
+
+use crate::file_system::FileSystemPath;
+use crate::FxDashMap;
+use crate::Db;
+
+pub mod file_set;
+mod path;
+
+pub use path::{VendoredPath, VendoredPathBuf, VfsPath, VirtualPath};
+
+/// Uniquely identifies a [`VfsPath`] that has been interned into a [`Vfs`].
+///
+/// `FileId`s are small, `Copy` and stable for the lifetime of the [`Vfs`] they were interned
+/// into, so they're cheap to use as a map key or a Salsa query input, unlike [`VfsPath`] which
+/// owns a full path string.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct FileId(u32);
+
+/// Bidirectional interner from [`VfsPath`] to [`FileId`].
+///
+/// Every distinct path is assigned a small `u32`-backed id on first insertion; interning the
+/// same path again hands back the existing id rather than allocating a new one.
+#[derive(Debug, Default)]
+pub struct PathInterner {
+    by_path: FxHashMap<VfsPath, FileId>,
+    by_id: Vec<VfsPath>,
+}
+
+impl PathInterner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Interns `path`, returning its [`FileId`] and whether the path was newly inserted.
+    pub fn intern(&mut self, path: VfsPath) -> (FileId, bool) {
+        if let Some(id) = self.by_path.get(&path) {
+            return (*id, false);
+        }
+
+        let id = FileId(u32::try_from(self.by_id.len()).expect("more than u32::MAX interned paths"));
+        self.by_id.push(path.clone());
+        self.by_path.insert(path, id);
+
+        (id, true)
+    }
+
+    /// Returns the path that `id` was interned from.
+    ///
+    /// ## Panics
+    /// If `id` wasn't returned by a previous call to [`PathInterner::intern`] on `self`.
+    pub fn lookup(&self, id: FileId) -> &VfsPath {
+        &self.by_id[id.0 as usize]
+    }
+}
+
+/// The file revision, used by Salsa to decide whether a file's dependent queries must be
+/// recomputed. Two revisions that don't compare equal mean the file has changed.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct FileRevision(u64);
+
+impl FileRevision {
+    pub const fn zero() -> Self {
+        Self(0)
+    }
+
+    pub fn new(value: u64) -> Self {
+        Self(value)
+    }
+
+    pub fn as_u64(self) -> u64 {
+        self.0
+    }
+}
+
+/// A file that's either stored on the file system, vendored as part of ruff, or a virtual slice
+/// of another file's source text.
+///
+/// `VfsFile` is a Salsa input: it's a cheap, `Copy` handle, and reading its fields always goes
+/// through the current `Db`, which is what lets editing a file's content or revision invalidate
+/// exactly the tracked queries that depend on it.
+#[salsa::input]
+pub struct VfsFile {
+    /// The id of the interned [`VfsPath`] that this file was created for.
+    pub id: FileId,
+
+    /// The file's revision. Two `VfsFile`s with different revisions are considered changed by
+    /// Salsa, even if they share the same `id`.
+    pub revision: FileRevision,
+
+    /// Whether the file currently exists. Set to `false` once a loader or tree walk observes
+    /// that the underlying path has been deleted.
+    pub exists: bool,
+}
+
+impl VfsFile {
+    /// Returns the path of this file, resolved through the [`Vfs`]'s [`PathInterner`].
+    pub fn path(self, db: &dyn Db) -> VfsPath {
+        db.vfs().path(self.id(db))
+    }
+}
+
+#[derive(Debug, Default)]
+struct VfsInner {
+    interner: Mutex<PathInterner>,
+    files_by_id: FxDashMap<FileId, VfsFile>,
+    vendored: FxDashMap<VendoredPathBuf, Arc<str>>,
+}
+
+/// The virtual file system.
+///
+/// Interns every [`VfsPath`] it sees into a small [`FileId`] and hands out a stable [`VfsFile`]
+/// for each distinct path, so that the rest of the system can key off a cheap `Copy` handle
+/// instead of cloning and hashing full paths.
+#[derive(Debug, Default, Clone)]
+pub struct Vfs {
+    inner: Arc<VfsInner>,
+}
+
+impl Vfs {
+    /// Creates a cheap, `Arc`-backed copy of the `Vfs` for use in a Salsa snapshot.
+    pub fn snapshot(&self) -> Self {
+        self.clone()
+    }
+
+    /// Returns the path that `id` was interned from.
+    ///
+    /// ## Panics
+    /// If `id` was interned by a different [`Vfs`].
+    pub fn path(&self, id: FileId) -> VfsPath {
+        self.inner.interner.lock().unwrap().lookup(id).clone()
+    }
+
+    /// Interns `path` and returns the corresponding [`VfsFile`], creating it on first access.
+    fn file(&self, db: &dyn Db, path: VfsPath) -> VfsFile {
+        let (id, _) = self.inner.interner.lock().unwrap().intern(path);
+
+        if let Some(file) = self.inner.files_by_id.get(&id) {
+            return *file;
+        }
+
+        let file = VfsFile::new(db, id, FileRevision::zero(), true);
+        self.inner.files_by_id.insert(id, file);
+        file
+    }
+
+    /// Returns the [`VfsFile`] already interned for `path`, without creating one.
+    ///
+    /// Unlike [`Vfs::file`], this never creates a new `VfsFile`, which makes it the right lookup
+    /// for events like a deletion: a path that was never seen before has nothing to invalidate,
+    /// and interning it here would conjure up a `VfsFile` that doesn't exist on disk.
+    pub(crate) fn try_file(&self, path: &VfsPath) -> Option<VfsFile> {
+        let id = self.inner.interner.lock().unwrap().by_path.get(path).copied()?;
+
+        self.inner.files_by_id.get(&id).map(|file| *file)
+    }
+
+    /// Replaces the content of the stubbed-out vendored files, for use in tests.
+    pub fn stub_vendored<P, S>(&mut self, files: impl IntoIterator<Item = (P, S)>)
+    where
+        P: AsRef<VendoredPath>,
+        S: AsRef<str>,
+    {
+        self.inner.vendored.clear();
+
+        for (path, content) in files {
+            self.inner
+                .vendored
+                .insert(path.as_ref().to_path_buf(), Arc::from(content.as_ref()));
+        }
+    }
+
+    pub(crate) fn read_vendored(&self, path: &VendoredPath) -> Option<Arc<str>> {
+        self.inner
+            .vendored
+            .get(&path.to_path_buf())
+            .map(|entry| entry.value().clone())
+    }
+
+    /// Applies a batch of file observations discovered in one pass, e.g. by a directory walk or
+    /// the [`loader`](crate::loader) syncing up after a burst of file-system events.
+    ///
+    /// Each `path` is interned (creating its [`VfsFile`] on first sight) and its revision is
+    /// bumped; `exists` records whether the path was found to still be present, so a file that
+    /// was removed from disk is marked as deleted rather than dropped from the `Vfs` entirely.
+    /// This lets a whole directory's worth of changes be synced from a single call instead of one
+    /// `set_file_contents` call per file; each changed file still costs two Salsa input writes
+    /// (`revision` and `exists`), since `VfsFile` has no combined setter for the two.
+    pub fn set_file_contents(
+        &self,
+        db: &mut dyn Db,
+        changes: impl IntoIterator<Item = (VfsPath, bool)>,
+    ) {
+        for (path, exists) in changes {
+            let file = self.file(&*db, path);
+
+            let new_revision = FileRevision::new(file.revision(&*db).as_u64() + 1);
+            file.set_revision(db).to(new_revision);
+            file.set_exists(db).to(exists);
+        }
+    }
+}
+
+/// A relative path together with the file it should be resolved relative to.
+///
+/// Bundling the two together lets callers express "the module next to *this* file" as a single
+/// value, rather than threading an anchor file and a string through separately. Composes with
+/// [`FileSet::resolve_path`](file_set::FileSet::resolve_path), which does the same resolution
+/// scoped to a single partition of the `Vfs`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct AnchoredPath<'a> {
+    pub anchor: VfsFile,
+    pub path: &'a str,
+}
+
+impl<'a> AnchoredPath<'a> {
+    pub fn new(anchor: VfsFile, path: &'a str) -> Self {
+        Self { anchor, path }
+    }
+}
+
+/// Joins `relative` onto the directory containing `anchor`, normalizing `.` and `..` segments,
+/// and rebuilds a [`VfsPath`] of the same kind as `anchor`.
+///
+/// Returns `None` if `anchor` doesn't have a containing directory to resolve against, which is
+/// always the case for a [`VfsPath::Virtual`] anchor.
+pub(crate) fn join_normalized(anchor: &VfsPath, relative: &str) -> Option<VfsPath> {
+    let anchor_path = Utf8Path::new(anchor.as_str()?);
+    let directory = anchor_path.parent()?;
+
+    let mut normalized: Utf8PathBuf = directory.to_path_buf();
+
+    for component in Utf8Path::new(relative).components() {
+        match component {
+            Utf8Component::CurDir => {}
+            Utf8Component::ParentDir => {
+                normalized.pop();
+            }
+            Utf8Component::Normal(segment) => normalized.push(segment),
+            Utf8Component::RootDir | Utf8Component::Prefix(_) => normalized.push(component),
+        }
+    }
+
+    Some(match anchor {
+        VfsPath::FileSystem(_) => VfsPath::file_system(FileSystemPath::new(&normalized)),
+        VfsPath::Vendored(_) => VfsPath::from(VendoredPath::new(&normalized)),
+        VfsPath::Virtual(_) => unreachable!("handled by the early return above"),
+    })
+}
+
+/// Looks up a [`VfsFile`] for a path on the file system, creating it on first access.
+pub fn system_path_to_file(db: &dyn Db, path: impl AsRef<FileSystemPath>) -> Option<VfsFile> {
+    let path = path.as_ref();
+
+    if !db.file_system().exists(path) {
+        return None;
+    }
+
+    Some(db.vfs().file(db, VfsPath::file_system(path)))
+}
+
+/// Looks up a [`VfsFile`] for a vendored path, creating it on first access.
+pub fn vendored_path_to_file(db: &dyn Db, path: &VendoredPath) -> Option<VfsFile> {
+    db.vfs().read_vendored(path)?;
+
+    Some(db.vfs().file(db, VfsPath::from(path)))
+}
+
+/// Returns the [`VfsFile`] for the slice `range` of `parent`'s source text, creating it on first
+/// access.
+pub fn virtual_file(db: &dyn Db, parent: VfsFile, range: TextRange) -> VfsFile {
+    db.vfs().file(db, VfsPath::virtual_path(parent, range))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::file_system::FileSystemPath;
+    use crate::vfs::{PathInterner, VfsPath};
+
+    #[test]
+    fn intern_reuses_the_id_of_an_already_interned_path() {
+        let mut interner = PathInterner::new();
+        let path = VfsPath::file_system(FileSystemPath::new("a.py"));
+
+        let (first_id, first_inserted) = interner.intern(path.clone());
+        let (second_id, second_inserted) = interner.intern(path.clone());
+
+        assert!(first_inserted);
+        assert!(!second_inserted);
+        assert_eq!(first_id, second_id);
+        assert_eq!(interner.lookup(first_id), &path);
+    }
+
+    #[test]
+    fn intern_assigns_distinct_ids_to_distinct_paths() {
+        let mut interner = PathInterner::new();
+
+        let (a_id, _) = interner.intern(VfsPath::file_system(FileSystemPath::new("a.py")));
+        let (b_id, _) = interner.intern(VfsPath::file_system(FileSystemPath::new("b.py")));
+
+        assert_ne!(a_id, b_id);
+        assert_eq!(
+            interner.lookup(a_id),
+            &VfsPath::file_system(FileSystemPath::new("a.py"))
+        );
+        assert_eq!(
+            interner.lookup(b_id),
+            &VfsPath::file_system(FileSystemPath::new("b.py"))
+        );
+    }
+}
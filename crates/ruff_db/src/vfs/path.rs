@@ -2,8 +2,10 @@ use std::ops::Deref;
 use std::path::Path;
 
 use camino::{Utf8Path, Utf8PathBuf};
+use ruff_text_size::TextRange;
 
 use crate::file_system::{FileSystemPath, FileSystemPathBuf};
+use crate::vfs::VfsFile;
 
 #[repr(transparent)]
 #[derive(Debug, Eq, PartialEq, Hash)]
@@ -94,12 +96,14 @@ impl Deref for VendoredPathBuf {
 ///
 /// * a file stored on disk
 /// * a vendored file that ships as part of the ruff binary
-/// * Future: A virtual file that references a slice of another file. For example, the CSS code in a python file.
+/// * a virtual file that references a slice of another file. For example, the CSS code in a python file.
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum VfsPath {
     /// Path that points to a file on disk.
     FileSystem(FileSystemPathBuf),
     Vendored(VendoredPathBuf),
+    /// Path to a slice of another file's source text.
+    Virtual(VirtualPath),
 }
 
 impl VfsPath {
@@ -109,13 +113,19 @@ impl VfsPath {
         VfsPath::FileSystem(path.as_ref().to_path_buf())
     }
 
+    /// Create a new path to `range` of `parent`'s source text.
+    #[must_use]
+    pub fn virtual_path(parent: VfsFile, range: TextRange) -> Self {
+        VfsPath::Virtual(VirtualPath { parent, range })
+    }
+
     /// Returns `Some` if the path is a file system path that points to a path on disk.
     #[must_use]
     #[inline]
     pub fn into_file_system_path_buf(self) -> Option<FileSystemPathBuf> {
         match self {
             VfsPath::FileSystem(path) => Some(path),
-            VfsPath::Vendored(_) => None,
+            VfsPath::Vendored(_) | VfsPath::Virtual(_) => None,
         }
     }
 
@@ -124,7 +134,7 @@ impl VfsPath {
     pub fn as_file_system_path(&self) -> Option<&FileSystemPath> {
         match self {
             VfsPath::FileSystem(path) => Some(path.as_path()),
-            VfsPath::Vendored(_) => None,
+            VfsPath::Vendored(_) | VfsPath::Virtual(_) => None,
         }
     }
 
@@ -142,27 +152,69 @@ impl VfsPath {
         matches!(self, VfsPath::Vendored(_))
     }
 
+    /// Returns `true` if the path is a virtual path into another file's source text.
+    #[must_use]
+    #[inline]
+    pub const fn is_virtual_path(&self) -> bool {
+        matches!(self, VfsPath::Virtual(_))
+    }
+
     #[must_use]
     #[inline]
     pub fn as_vendored_path(&self) -> Option<&VendoredPath> {
         match self {
             VfsPath::Vendored(path) => Some(path.as_path()),
-            VfsPath::FileSystem(_) => None,
+            VfsPath::FileSystem(_) | VfsPath::Virtual(_) => None,
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn as_virtual_path(&self) -> Option<&VirtualPath> {
+        match self {
+            VfsPath::Virtual(virtual_path) => Some(virtual_path),
+            VfsPath::FileSystem(_) | VfsPath::Vendored(_) => None,
         }
     }
 
     /// Yields the underlying [`str`] slice.
-    pub fn as_str(&self) -> &str {
+    ///
+    /// Returns `None` for a [`VfsPath::Virtual`] path, which doesn't have a string representation
+    /// of its own: it's identified by its parent file and a range into it, not by a path.
+    pub fn as_str(&self) -> Option<&str> {
         match self {
-            VfsPath::FileSystem(path) => path.as_str(),
-            VfsPath::Vendored(path) => path.as_str(),
+            VfsPath::FileSystem(path) => Some(path.as_str()),
+            VfsPath::Vendored(path) => Some(path.as_str()),
+            VfsPath::Virtual(_) => None,
         }
     }
 }
 
+/// A path into a slice of another file's source text, e.g. the CSS code embedded in a Python
+/// string or the Python code inside a docstring's doctest.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct VirtualPath {
+    parent: VfsFile,
+    range: TextRange,
+}
+
+impl VirtualPath {
+    /// The file that this virtual path is a slice of.
+    pub fn parent(&self) -> VfsFile {
+        self.parent
+    }
+
+    /// The range of the parent's source text that this virtual path refers to.
+    pub fn range(&self) -> TextRange {
+        self.range
+    }
+}
+
 impl AsRef<str> for VfsPath {
+    /// Returns an empty string placeholder for a [`VfsPath::Virtual`] path; use
+    /// [`VfsPath::as_str`] directly if the distinction matters to the caller.
     fn as_ref(&self) -> &str {
-        self.as_str()
+        self.as_str().unwrap_or_default()
     }
 }
 